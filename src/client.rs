@@ -1,15 +1,25 @@
 use chrono::{DateTime, Utc};
 use failure::Fail;
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use rust_decimal::Decimal;
 use serde::de::Deserializer;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use sha2::Sha256;
+use std::cell::RefCell;
 use std::fmt;
 use std::fmt::Display;
 use std::str;
 use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+type HmacSha256 = Hmac<Sha256>;
 
 pub struct Client {
     pub url: String,
+    pub secret: Option<String>,
+    http: reqwest::blocking::Client,
 }
 
 #[derive(Deserialize, Debug)]
@@ -41,35 +51,126 @@ pub struct Quote {
     pub product_id: String,
     pub base_currency: String,
     #[serde(deserialize_with = "from_str")]
-    pub price: f32,
+    pub price: Decimal,
     #[serde(deserialize_with = "from_str")]
-    pub base_currency_size: f32,
+    pub base_currency_size: Decimal,
     #[serde(deserialize_with = "from_str")]
-    pub quote_currency_size: f32,
+    pub quote_currency_size: Decimal,
     pub side: Side,
     pub created_at: DateTime<Utc>,
     pub expiry: DateTime<Utc>,
 }
 
+impl Quote {
+    pub fn is_expired(&self) -> bool {
+        Utc::now() >= self.expiry
+    }
+}
+
+/// `order_status`/`status` as reported by the server. `Unknown` keeps
+/// deserialization forward-compatible with states the API adds later,
+/// rather than failing the whole `Order` decode.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrderStatus {
+    Pending,
+    Open,
+    Filled,
+    PartiallyFilled,
+    Cancelled,
+    Rejected,
+    Unknown(String),
+}
+
+impl OrderStatus {
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            OrderStatus::Filled | OrderStatus::Cancelled | OrderStatus::Rejected
+        )
+    }
+}
+
+impl<'de> Deserialize<'de> for OrderStatus {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Ok(match s.as_str() {
+            "pending" => OrderStatus::Pending,
+            "open" => OrderStatus::Open,
+            "filled" => OrderStatus::Filled,
+            "partially_filled" => OrderStatus::PartiallyFilled,
+            "cancelled" => OrderStatus::Cancelled,
+            "rejected" => OrderStatus::Rejected,
+            _ => OrderStatus::Unknown(s),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrderType {
+    Market,
+    Limit,
+    Unknown(String),
+}
+
+impl<'de> Deserialize<'de> for OrderType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Ok(match s.as_str() {
+            "market" => OrderType::Market,
+            "limit" => OrderType::Limit,
+            _ => OrderType::Unknown(s),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimeInForce {
+    GoodTilCancelled,
+    ImmediateOrCancel,
+    FillOrKill,
+    Unknown(String),
+}
+
+impl<'de> Deserialize<'de> for TimeInForce {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Ok(match s.as_str() {
+            "gtc" => TimeInForce::GoodTilCancelled,
+            "ioc" => TimeInForce::ImmediateOrCancel,
+            "fok" => TimeInForce::FillOrKill,
+            _ => TimeInForce::Unknown(s),
+        })
+    }
+}
+
 #[derive(Deserialize, Debug)]
 pub struct Order {
     pub id: String,
     pub product_id: String,
-    pub order_type: String,
-    pub order_status: String,
-    pub time_in_force: String,
+    pub order_type: OrderType,
+    pub order_status: OrderStatus,
+    pub time_in_force: TimeInForce,
     #[serde(deserialize_with = "from_str")]
-    pub fill_price: f32,
+    pub fill_price: Decimal,
     #[serde(deserialize_with = "from_str")]
-    pub fill_qty: f32,
+    pub fill_qty: Decimal,
     #[serde(deserialize_with = "from_str")]
-    pub price: f32,
+    pub price: Decimal,
     #[serde(deserialize_with = "from_str")]
-    pub order_size: f32,
+    pub order_size: Decimal,
     pub client_side: Side,
-    pub status: String,
+    pub status: OrderStatus,
     #[serde(deserialize_with = "from_str")]
-    pub executed_value: f32,
+    pub executed_value: Decimal,
 }
 
 #[derive(Deserialize, Debug)]
@@ -99,6 +200,14 @@ pub enum Error {
     NetworkingError(#[cause] reqwest::Error),
     #[fail(display = "EscherError - {}", _0)]
     HandledError(EscherError),
+    #[fail(display = "Unauthorized - {}", _0)]
+    Unauthorized(EscherError),
+    #[fail(display = "malformed access token: {}", _0)]
+    MalformedToken(String),
+    #[fail(display = "quote {} has expired", _0)]
+    QuoteExpired(String),
+    #[fail(display = "order {} did not reach a terminal state within the timeout", _0)]
+    PollTimeout(String),
 }
 
 impl From<reqwest::Error> for Error {
@@ -114,60 +223,173 @@ impl From<serde_json::Error> for Error {
 }
 type EscherResult<Data> = Result<Data, Error>;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "lowercase")]
 pub enum Side {
     Buy,
     Sell,
 }
 
+/// Headers attached to a request signed with [`sign_request`].
+struct Signature {
+    signature: String,
+    timestamp: String,
+    nonce: String,
+}
+
+fn generate_nonce() -> String {
+    let bytes: [u8; 16] = rand::thread_rng().gen();
+    hex::encode(bytes)
+}
+
+/// `HMAC-SHA256(secret, method || "\n" || path || "\n" || timestamp || "\n"
+/// || nonce || "\n" || body)`, hex-encoded. Split out from [`sign_request`]
+/// so the canonical string construction can be exercised with fixed
+/// timestamp/nonce inputs.
+///
+/// The `\n` separators are load-bearing: without them, concatenating the
+/// fields back-to-back is ambiguous (e.g. `method="GET"` + `path="/orders/1"`
+/// + `timestamp="23000"` collides byte-for-byte with `path="/orders/12"` +
+/// `timestamp="3000"`), which would let one signed request's signature be
+/// replayed against a different request that happens to canonicalize to the
+/// same bytes. `\n` can't appear in `method`, and HTTP request paths, our
+/// decimal timestamps and hex nonces never contain it either, so each field stays
+/// unambiguously delimited.
+fn canonical_signature(
+    secret: &str,
+    method: &str,
+    path: &str,
+    timestamp: &str,
+    nonce: &str,
+    body: &[u8],
+) -> String {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts a key of any size");
+    mac.update(method.as_bytes());
+    mac.update(b"\n");
+    mac.update(path.as_bytes());
+    mac.update(b"\n");
+    mac.update(timestamp.as_bytes());
+    mac.update(b"\n");
+    mac.update(nonce.as_bytes());
+    mac.update(b"\n");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Builds the `i2-SIGNATURE`/`i2-TIMESTAMP`/`i2-NONCE` headers for an
+/// authenticated request. The caller must sign the exact bytes it sends on
+/// the wire.
+fn sign_request(secret: &str, method: &str, path: &str, body: &[u8]) -> Signature {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock is before the unix epoch")
+        .as_millis()
+        .to_string();
+    let nonce = generate_nonce();
+    let signature = canonical_signature(secret, method, path, &timestamp, &nonce, body);
+
+    Signature {
+        signature,
+        timestamp,
+        nonce,
+    }
+}
+
+/// Header name/value pairs to attach when `secret` is configured; empty
+/// otherwise. Shared by [`Client`] and [`AsyncClient`] so signing can't
+/// drift between the two transports.
+fn signing_headers(
+    secret: Option<&str>,
+    method: &str,
+    path: &str,
+    body: &[u8],
+) -> Vec<(&'static str, String)> {
+    match secret {
+        Some(secret) => {
+            let sig = sign_request(secret, method, path, body);
+            vec![
+                ("i2-SIGNATURE", sig.signature),
+                ("i2-TIMESTAMP", sig.timestamp),
+                ("i2-NONCE", sig.nonce),
+            ]
+        }
+        None => Vec::new(),
+    }
+}
+
+/// Decodes `value` as `T`, falling back to the server's `EscherError` body
+/// when it doesn't match. A 401/403 `status` maps to `Error::Unauthorized`
+/// rather than `Error::HandledError` so callers (namely `Session`) can tell
+/// an auth rejection apart from any other business-rule rejection, which
+/// uses the exact same `{success, message}` shape. Shared by every
+/// [`Client`]/[`AsyncClient`] method so the fallback dance isn't
+/// hand-duplicated per endpoint.
+fn decode_or_error<T: serde::de::DeserializeOwned>(
+    status: reqwest::StatusCode,
+    value: serde_json::Value,
+) -> EscherResult<T> {
+    serde_json::from_value::<T>(value.clone()).map_err(|_| {
+        match serde_json::from_value::<EscherError>(value) {
+            Ok(err) if status == reqwest::StatusCode::UNAUTHORIZED
+                || status == reqwest::StatusCode::FORBIDDEN =>
+            {
+                Error::Unauthorized(err)
+            }
+            Ok(err) => Error::HandledError(err),
+            Err(err) => err.into(),
+        }
+    })
+}
+
 impl Client {
     pub fn init(url: String) -> Self {
-        Self { url }
+        let http = reqwest::blocking::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .tcp_keepalive(Duration::from_secs(60))
+            .build()
+            .expect("failed to build reqwest client");
+
+        Self {
+            url,
+            secret: None,
+            http,
+        }
+    }
+
+    /// Enables HMAC-SHA256 request signing for authenticated endpoints.
+    pub fn with_secret(mut self, secret: String) -> Self {
+        self.secret = Some(secret);
+        self
     }
 
     pub fn sign_in(&self, email: String, password: String) -> EscherResult<AuthResponse> {
-        let resp = reqwest::blocking::Client::new()
+        let resp = self
+            .http
             .post(&format!("{}/sign-in", self.url))
             .json(&json!({"email": email, "password": password}))
             .send()?;
 
-        let json = resp.json::<serde_json::Value>();
-
-        match json {
-            Ok(auth) => serde_json::from_value::<AuthResponse>(auth.clone()).map_err(|_| {
-                match serde_json::from_value::<EscherError>(auth) {
-                    Ok(err) => Error::HandledError(err),
-                    Err(err) => err.into(),
-                }
-            }),
-            Err(err) => Err(err.into()),
-        }
+        let status = resp.status();
+        decode_or_error(status, resp.json::<serde_json::Value>()?)
     }
 
     pub fn refresh_token(&self, token: String, email: String) -> EscherResult<AuthResponse> {
-        let resp = reqwest::blocking::Client::new()
+        let resp = self
+            .http
             .post(&format!("{}/sign-in", self.url))
             .json(&json!({ "refreshToken": token, "email": email }))
             .send()?;
 
-        let json = resp.json::<serde_json::Value>();
-        match json {
-            Ok(auth) => serde_json::from_value::<AuthResponse>(auth.clone()).map_err(|_| {
-                match serde_json::from_value::<EscherError>(auth) {
-                    Ok(err) => Error::HandledError(err),
-                    Err(err) => err.into(),
-                }
-            }),
-            Err(err) => Err(err.into()),
-        }
+        let status = resp.status();
+        decode_or_error(status, resp.json::<serde_json::Value>()?)
     }
 
     pub fn quote(
         &self,
         access_token: String,
         product_id: String,
-        base_currency_size: String,
+        base_currency_size: Decimal,
         side: Side,
     ) -> EscherResult<Quote> {
         let params = json!({
@@ -175,42 +397,546 @@ impl Client {
             "base_currency_size": base_currency_size,
             "side": side
         });
+        let body = serde_json::to_vec(&params)?;
 
-        let resp = reqwest::blocking::Client::new()
+        let mut req = self
+            .http
             .post(&format!("{}/quotes", self.url))
-            .json(&params)
-            .header("i2-ACCESS-KEY", access_token)
-            .send()?;
+            .header("Content-Type", "application/json")
+            .header("i2-ACCESS-KEY", access_token);
 
-        let quote = resp.json::<serde_json::Value>()?;
+        for (name, value) in signing_headers(self.secret.as_deref(), "POST", "/quotes", &body) {
+            req = req.header(name, value);
+        }
 
-        serde_json::from_value::<Quote>(quote.clone()).map_err(|_| {
-            match serde_json::from_value::<EscherError>(quote) {
-                Ok(err) => Error::HandledError(err),
-                Err(err) => err.into(),
-            }
-        })
+        let resp = req.body(body).send()?;
+
+        let status = resp.status();
+        decode_or_error(status, resp.json::<serde_json::Value>()?)
     }
 
     pub fn accept_quote(
         &self,
         access_token: String,
-        quote_id: String,
-        quantity: Option<f32>,
+        quote: &Quote,
+        quantity: Option<Decimal>,
     ) -> EscherResult<AcceptQuote> {
-        let resp = reqwest::blocking::Client::new()
+        if quote.is_expired() {
+            return Err(Error::QuoteExpired(quote.quote_id.clone()));
+        }
+
+        let body =
+            serde_json::to_vec(&json!({"quote_id": quote.quote_id, "quantity" : quantity}))?;
+
+        let mut req = self
+            .http
             .post(&format!("{}/quotes/accept", self.url))
-            .json(&json!({"quote_id": quote_id, "quantity" : quantity}))
-            .header("i2-ACCESS-KEY", access_token)
-            .send()?;
+            .header("Content-Type", "application/json")
+            .header("i2-ACCESS-KEY", access_token);
+
+        for (name, value) in
+            signing_headers(self.secret.as_deref(), "POST", "/quotes/accept", &body)
+        {
+            req = req.header(name, value);
+        }
+
+        let resp = req.body(body).send()?;
+
+        let status = resp.status();
+        decode_or_error(status, resp.json::<serde_json::Value>()?)
+    }
+
+    fn get_order(&self, access_token: &str, order_id: &str) -> EscherResult<Order> {
+        let path = format!("/orders/{}", order_id);
+        let mut req = self
+            .http
+            .get(&format!("{}{}", self.url, path))
+            .header("i2-ACCESS-KEY", access_token);
+
+        for (name, value) in signing_headers(self.secret.as_deref(), "GET", &path, &[]) {
+            req = req.header(name, value);
+        }
+
+        let resp = req.send()?;
+
+        let status = resp.status();
+        decode_or_error(status, resp.json::<serde_json::Value>()?)
+    }
+
+    /// Polls an order until `order_status` reaches a terminal state
+    /// (filled, cancelled or rejected), or returns `Error::PollTimeout` once
+    /// `timeout` elapses.
+    pub fn poll_order(
+        &self,
+        access_token: String,
+        order_id: String,
+        interval: Duration,
+        timeout: Duration,
+    ) -> EscherResult<Order> {
+        let deadline = std::time::Instant::now() + timeout;
 
-        let quote = resp.json::<serde_json::Value>()?;
+        loop {
+            let order = self.get_order(&access_token, &order_id)?;
+            if order.order_status.is_terminal() {
+                return Ok(order);
+            }
 
-        serde_json::from_value::<AcceptQuote>(quote.clone()).map_err(|_| {
-            match serde_json::from_value::<EscherError>(quote) {
-                Ok(err) => Error::HandledError(err),
-                Err(err) => err.into(),
+            if std::time::Instant::now() >= deadline {
+                return Err(Error::PollTimeout(order_id));
             }
-        })
+
+            std::thread::sleep(interval);
+        }
+    }
+}
+
+/// Async counterpart of [`Client`], backed by `reqwest::Client` so a tokio
+/// runtime can drive many in-flight requests (e.g. concurrent quote polling)
+/// without blocking worker threads.
+pub struct AsyncClient {
+    pub url: String,
+    pub secret: Option<String>,
+    http: reqwest::Client,
+}
+
+impl AsyncClient {
+    pub fn init(url: String) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .tcp_keepalive(Duration::from_secs(60))
+            .build()
+            .expect("failed to build reqwest client");
+
+        Self {
+            url,
+            secret: None,
+            http,
+        }
+    }
+
+    /// Enables HMAC-SHA256 request signing for authenticated endpoints.
+    pub fn with_secret(mut self, secret: String) -> Self {
+        self.secret = Some(secret);
+        self
+    }
+
+    pub async fn sign_in(&self, email: String, password: String) -> EscherResult<AuthResponse> {
+        let resp = self
+            .http
+            .post(&format!("{}/sign-in", self.url))
+            .json(&json!({"email": email, "password": password}))
+            .send()
+            .await?;
+
+        let status = resp.status();
+        decode_or_error(status, resp.json::<serde_json::Value>().await?)
+    }
+
+    pub async fn refresh_token(&self, token: String, email: String) -> EscherResult<AuthResponse> {
+        let resp = self
+            .http
+            .post(&format!("{}/sign-in", self.url))
+            .json(&json!({ "refreshToken": token, "email": email }))
+            .send()
+            .await?;
+
+        let status = resp.status();
+        decode_or_error(status, resp.json::<serde_json::Value>().await?)
+    }
+
+    pub async fn quote(
+        &self,
+        access_token: String,
+        product_id: String,
+        base_currency_size: Decimal,
+        side: Side,
+    ) -> EscherResult<Quote> {
+        let params = json!({
+            "product_id": product_id,
+            "base_currency_size": base_currency_size,
+            "side": side
+        });
+        let body = serde_json::to_vec(&params)?;
+
+        let mut req = self
+            .http
+            .post(&format!("{}/quotes", self.url))
+            .header("Content-Type", "application/json")
+            .header("i2-ACCESS-KEY", access_token);
+
+        for (name, value) in signing_headers(self.secret.as_deref(), "POST", "/quotes", &body) {
+            req = req.header(name, value);
+        }
+
+        let resp = req.body(body).send().await?;
+
+        let status = resp.status();
+        decode_or_error(status, resp.json::<serde_json::Value>().await?)
+    }
+
+    pub async fn accept_quote(
+        &self,
+        access_token: String,
+        quote: &Quote,
+        quantity: Option<Decimal>,
+    ) -> EscherResult<AcceptQuote> {
+        if quote.is_expired() {
+            return Err(Error::QuoteExpired(quote.quote_id.clone()));
+        }
+
+        let body =
+            serde_json::to_vec(&json!({"quote_id": quote.quote_id, "quantity" : quantity}))?;
+
+        let mut req = self
+            .http
+            .post(&format!("{}/quotes/accept", self.url))
+            .header("Content-Type", "application/json")
+            .header("i2-ACCESS-KEY", access_token);
+
+        for (name, value) in
+            signing_headers(self.secret.as_deref(), "POST", "/quotes/accept", &body)
+        {
+            req = req.header(name, value);
+        }
+
+        let resp = req.body(body).send().await?;
+
+        let status = resp.status();
+        decode_or_error(status, resp.json::<serde_json::Value>().await?)
+    }
+
+    async fn get_order(&self, access_token: &str, order_id: &str) -> EscherResult<Order> {
+        let path = format!("/orders/{}", order_id);
+        let mut req = self
+            .http
+            .get(&format!("{}{}", self.url, path))
+            .header("i2-ACCESS-KEY", access_token);
+
+        for (name, value) in signing_headers(self.secret.as_deref(), "GET", &path, &[]) {
+            req = req.header(name, value);
+        }
+
+        let resp = req.send().await?;
+
+        let status = resp.status();
+        decode_or_error(status, resp.json::<serde_json::Value>().await?)
+    }
+
+    /// Polls an order until `order_status` reaches a terminal state
+    /// (filled, cancelled or rejected), or returns `Error::PollTimeout` once
+    /// `timeout` elapses.
+    pub async fn poll_order(
+        &self,
+        access_token: String,
+        order_id: String,
+        interval: Duration,
+        timeout: Duration,
+    ) -> EscherResult<Order> {
+        let deadline = std::time::Instant::now() + timeout;
+
+        loop {
+            let order = self.get_order(&access_token, &order_id).await?;
+            if order.order_status.is_terminal() {
+                return Ok(order);
+            }
+
+            if std::time::Instant::now() >= deadline {
+                return Err(Error::PollTimeout(order_id));
+            }
+
+            tokio::time::sleep(interval).await;
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct Claims {
+    exp: i64,
+}
+
+/// Reads the `exp` claim out of a JWT without verifying its signature —
+/// we only need it to decide whether our own access token is stale.
+fn decode_expiry(access_token: &str) -> EscherResult<DateTime<Utc>> {
+    let segments: Vec<&str> = access_token.split('.').collect();
+    let payload = match segments.as_slice() {
+        [_, payload, _] => *payload,
+        _ => return Err(Error::MalformedToken("access token is not a JWT".into())),
+    };
+
+    let decoded = base64::decode_config(payload, base64::URL_SAFE_NO_PAD)
+        .map_err(|err| Error::MalformedToken(err.to_string()))?;
+    let claims: Claims =
+        serde_json::from_slice(&decoded).map_err(|err| Error::MalformedToken(err.to_string()))?;
+
+    DateTime::from_timestamp(claims.exp, 0)
+        .ok_or_else(|| Error::MalformedToken(format!("exp {} is out of range", claims.exp)))
+}
+
+/// Owns a pair of tokens and keeps them fresh. `Session::quote` /
+/// `Session::accept_quote` renew the access token before it expires (within
+/// `skew` of its JWT `exp`), so callers never juggle token strings by hand.
+pub struct Session<'a> {
+    client: &'a Client,
+    email: String,
+    skew: chrono::Duration,
+    tokens: RefCell<AuthResult>,
+}
+
+impl<'a> Session<'a> {
+    pub fn new(client: &'a Client, email: String, tokens: AuthResult) -> Self {
+        Self {
+            client,
+            email,
+            skew: chrono::Duration::seconds(30),
+            tokens: RefCell::new(tokens),
+        }
+    }
+
+    /// Overrides the default 30 second renewal window.
+    pub fn with_skew(mut self, skew: chrono::Duration) -> Self {
+        self.skew = skew;
+        self
+    }
+
+    fn is_expiring(&self) -> EscherResult<bool> {
+        let expiry = decode_expiry(&self.tokens.borrow().access_token)?;
+        Ok(Utc::now() + self.skew >= expiry)
+    }
+
+    fn renew(&self) -> EscherResult<()> {
+        let refresh_token = self.tokens.borrow().refresh_token.clone();
+        let auth = self
+            .client
+            .refresh_token(refresh_token, self.email.clone())?;
+        *self.tokens.borrow_mut() = auth.authentication_result;
+        Ok(())
+    }
+
+    fn ensure_fresh(&self) -> EscherResult<()> {
+        if self.is_expiring()? {
+            self.renew()?;
+        }
+        Ok(())
+    }
+
+    fn access_token(&self) -> String {
+        self.tokens.borrow().access_token.clone()
+    }
+
+    pub fn quote(
+        &self,
+        product_id: String,
+        base_currency_size: Decimal,
+        side: Side,
+    ) -> EscherResult<Quote> {
+        self.ensure_fresh()?;
+
+        match self.client.quote(
+            self.access_token(),
+            product_id.clone(),
+            base_currency_size,
+            side.clone(),
+        ) {
+            Err(Error::Unauthorized(_)) => {
+                self.renew()?;
+                self.client
+                    .quote(self.access_token(), product_id, base_currency_size, side)
+            }
+            result => result,
+        }
+    }
+
+    pub fn accept_quote(&self, quote: &Quote, quantity: Option<Decimal>) -> EscherResult<AcceptQuote> {
+        self.ensure_fresh()?;
+
+        match self.client.accept_quote(self.access_token(), quote, quantity) {
+            Err(Error::Unauthorized(_)) => {
+                self.renew()?;
+                self.client.accept_quote(self.access_token(), quote, quantity)
+            }
+            result => result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonical_signature_matches_known_hmac_sha256_vector() {
+        let signature = canonical_signature(
+            "test-secret",
+            "POST",
+            "/quotes",
+            "1700000000000",
+            "nonce-123",
+            br#"{"product_id":"BTC-USD"}"#,
+        );
+
+        assert_eq!(
+            signature,
+            "a8b687fe2f4b8cd2dd80c88a0e7e45483a298f61f0285254d903949f644aef9e"
+        );
+    }
+
+    #[test]
+    fn canonical_signature_changes_with_body() {
+        let a = canonical_signature("secret", "POST", "/quotes", "1", "n", b"{}");
+        let b = canonical_signature("secret", "POST", "/quotes", "1", "n", b"{\"x\":1}");
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn canonical_signature_is_injective_across_the_path_timestamp_boundary() {
+        // Without a field separator, ("GET", "/orders/1", "23000", ...) and
+        // ("GET", "/orders/12", "3000", ...) would concatenate to the same
+        // bytes: "GET" + "/orders/1" + "23000" == "GET" + "/orders/12" + "3000".
+        let a = canonical_signature("k", "GET", "/orders/1", "23000", "nonceX", b"{}");
+        let b = canonical_signature("k", "GET", "/orders/12", "3000", "nonceX", b"{}");
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn signing_headers_empty_without_secret() {
+        assert!(signing_headers(None, "POST", "/quotes", b"{}").is_empty());
+    }
+
+    #[test]
+    fn signing_headers_present_with_secret() {
+        let headers = signing_headers(Some("secret"), "POST", "/quotes", b"{}");
+        let names: Vec<_> = headers.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, vec!["i2-SIGNATURE", "i2-TIMESTAMP", "i2-NONCE"]);
+    }
+
+    #[test]
+    fn decode_expiry_reads_the_exp_claim() {
+        // header.payload.signature where payload is {"exp": 2000000000}
+        let token = "header.eyJleHAiOiAyMDAwMDAwMDAwfQ.signature";
+        let expiry = decode_expiry(token).unwrap();
+        assert_eq!(expiry.timestamp(), 2_000_000_000);
+    }
+
+    #[test]
+    fn decode_expiry_rejects_a_token_without_three_segments() {
+        assert!(matches!(
+            decode_expiry("not-a-jwt"),
+            Err(Error::MalformedToken(_))
+        ));
+    }
+
+    #[test]
+    fn decode_expiry_rejects_non_base64_payload() {
+        assert!(matches!(
+            decode_expiry("header.not!base64url.signature"),
+            Err(Error::MalformedToken(_))
+        ));
+    }
+
+    #[test]
+    fn decode_expiry_rejects_out_of_range_exp_instead_of_panicking() {
+        // i64::MAX seconds overflows DateTime<Utc>'s representable range.
+        let payload = base64::encode_config(
+            format!(r#"{{"exp":{}}}"#, i64::MAX),
+            base64::URL_SAFE_NO_PAD,
+        );
+        let token = format!("header.{}.signature", payload);
+
+        assert!(matches!(decode_expiry(&token), Err(Error::MalformedToken(_))));
+    }
+
+    fn sample_quote(expiry: DateTime<Utc>) -> Quote {
+        Quote {
+            quote_id: "quote-1".into(),
+            product_id: "BTC-USD".into(),
+            base_currency: "BTC".into(),
+            price: Decimal::new(1, 0),
+            base_currency_size: Decimal::new(1, 0),
+            quote_currency_size: Decimal::new(1, 0),
+            side: Side::Buy,
+            created_at: Utc::now(),
+            expiry,
+        }
+    }
+
+    #[test]
+    fn quote_is_expired_once_past_its_expiry() {
+        let quote = sample_quote(Utc::now() - chrono::Duration::seconds(1));
+        assert!(quote.is_expired());
+    }
+
+    #[test]
+    fn quote_is_not_expired_before_its_expiry() {
+        let quote = sample_quote(Utc::now() + chrono::Duration::seconds(60));
+        assert!(!quote.is_expired());
+    }
+
+    #[test]
+    fn order_status_deserializes_known_variants() {
+        let cases = [
+            (r#""pending""#, OrderStatus::Pending),
+            (r#""open""#, OrderStatus::Open),
+            (r#""filled""#, OrderStatus::Filled),
+            (r#""partially_filled""#, OrderStatus::PartiallyFilled),
+            (r#""cancelled""#, OrderStatus::Cancelled),
+            (r#""rejected""#, OrderStatus::Rejected),
+        ];
+
+        for (json, expected) in cases {
+            assert_eq!(serde_json::from_str::<OrderStatus>(json).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn order_status_falls_back_to_unknown_for_new_states() {
+        let status: OrderStatus = serde_json::from_str(r#""expired""#).unwrap();
+        assert_eq!(status, OrderStatus::Unknown("expired".into()));
+    }
+
+    #[test]
+    fn order_status_is_terminal_only_for_filled_cancelled_rejected() {
+        assert!(OrderStatus::Filled.is_terminal());
+        assert!(OrderStatus::Cancelled.is_terminal());
+        assert!(OrderStatus::Rejected.is_terminal());
+        assert!(!OrderStatus::Pending.is_terminal());
+        assert!(!OrderStatus::Open.is_terminal());
+        assert!(!OrderStatus::PartiallyFilled.is_terminal());
+        assert!(!OrderStatus::Unknown("expired".into()).is_terminal());
+    }
+
+    #[test]
+    fn order_type_deserializes_known_variants_and_falls_back_to_unknown() {
+        assert_eq!(
+            serde_json::from_str::<OrderType>(r#""market""#).unwrap(),
+            OrderType::Market
+        );
+        assert_eq!(
+            serde_json::from_str::<OrderType>(r#""limit""#).unwrap(),
+            OrderType::Limit
+        );
+        assert_eq!(
+            serde_json::from_str::<OrderType>(r#""stop""#).unwrap(),
+            OrderType::Unknown("stop".into())
+        );
+    }
+
+    #[test]
+    fn time_in_force_deserializes_known_variants_and_falls_back_to_unknown() {
+        assert_eq!(
+            serde_json::from_str::<TimeInForce>(r#""gtc""#).unwrap(),
+            TimeInForce::GoodTilCancelled
+        );
+        assert_eq!(
+            serde_json::from_str::<TimeInForce>(r#""ioc""#).unwrap(),
+            TimeInForce::ImmediateOrCancel
+        );
+        assert_eq!(
+            serde_json::from_str::<TimeInForce>(r#""fok""#).unwrap(),
+            TimeInForce::FillOrKill
+        );
+        assert_eq!(
+            serde_json::from_str::<TimeInForce>(r#""day""#).unwrap(),
+            TimeInForce::Unknown("day".into())
+        );
     }
 }